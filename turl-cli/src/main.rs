@@ -0,0 +1,157 @@
+use std::env;
+use std::process::ExitCode;
+
+use turl_core::service::server;
+use turl_core::{
+    MessageRole, OutputFormat, ProviderKind, ProviderRoots, SearchOptions, ThreadUri, read_thread_raw, render_thread,
+    search_threads,
+};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("serve") => run_server(),
+        Some("search") => run_search(&args[1..]),
+        _ => run(&args),
+    }
+}
+
+fn run_server() -> ExitCode {
+    match server::main_loop() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(source) => {
+            eprintln!("turl: server error: {source}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> ExitCode {
+    let mut uri_arg = None;
+    let mut raw = false;
+    let mut format = OutputFormat::Markdown;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--raw" => raw = true,
+            "--format" => {
+                let Some(value) = iter.next() else {
+                    eprintln!("turl: --format requires a value (markdown|json|html)");
+                    return ExitCode::FAILURE;
+                };
+                let Some(parsed) = OutputFormat::parse(value) else {
+                    eprintln!("turl: unknown format `{value}` (expected markdown, json, or html)");
+                    return ExitCode::FAILURE;
+                };
+                format = parsed;
+            }
+            other if uri_arg.is_none() => uri_arg = Some(other.to_string()),
+            other => {
+                eprintln!("turl: unexpected argument `{other}`");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(uri_arg) = uri_arg else {
+        eprintln!("turl: usage: turl <uri> [--format markdown|json|html] [--raw]");
+        return ExitCode::FAILURE;
+    };
+
+    let uri = match ThreadUri::parse(&uri_arg) {
+        Ok(uri) => uri,
+        Err(source) => {
+            eprintln!("turl: {source}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let roots = ProviderRoots::discover();
+    // `--format` supersedes the legacy `--raw`/default split; `--raw` stays
+    // as a shorthand for dumping the untouched source file.
+    let output = if raw {
+        read_thread_raw(&uri, &roots)
+    } else {
+        render_thread(&uri, format, &roots)
+    };
+
+    match output {
+        Ok(text) => {
+            println!("{text}");
+            ExitCode::SUCCESS
+        }
+        Err(source) => {
+            eprintln!("turl: {source}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_search(args: &[String]) -> ExitCode {
+    let mut query_arg = None;
+    let mut opts = SearchOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--regex" => opts.regex = true,
+            "--provider" => {
+                let Some(value) = iter.next() else {
+                    eprintln!("turl: --provider requires a value (codex|claude)");
+                    return ExitCode::FAILURE;
+                };
+                opts.provider = match value.as_str() {
+                    "codex" => Some(ProviderKind::Codex),
+                    "claude" => Some(ProviderKind::Claude),
+                    other => {
+                        eprintln!("turl: unknown provider `{other}` (expected codex or claude)");
+                        return ExitCode::FAILURE;
+                    }
+                };
+            }
+            "--role" => {
+                let Some(value) = iter.next() else {
+                    eprintln!("turl: --role requires a value (user|assistant)");
+                    return ExitCode::FAILURE;
+                };
+                opts.role = match value.as_str() {
+                    "user" => Some(MessageRole::User),
+                    "assistant" => Some(MessageRole::Assistant),
+                    other => {
+                        eprintln!("turl: unknown role `{other}` (expected user or assistant)");
+                        return ExitCode::FAILURE;
+                    }
+                };
+            }
+            other if query_arg.is_none() => query_arg = Some(other.to_string()),
+            other => {
+                eprintln!("turl: unexpected argument `{other}`");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(query) = query_arg else {
+        eprintln!("turl: usage: turl search <query> [--regex] [--provider codex|claude] [--role user|assistant]");
+        return ExitCode::FAILURE;
+    };
+
+    let roots = ProviderRoots::discover();
+    match search_threads(&query, &opts, &roots) {
+        Ok(results) => {
+            for hit in &results.hits {
+                println!("{}:{} [{:?}] {}", hit.provider, hit.session_id, hit.role, hit.snippet);
+            }
+            for warning in &results.warnings {
+                eprintln!("turl: skipped unparseable file {}", warning.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(source) => {
+            eprintln!("turl: {source}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -37,6 +37,39 @@ fn default_outputs_markdown() {
         .stdout(predicate::str::contains("hello"));
 }
 
+#[test]
+fn format_json_outputs_structured_schema() {
+    let (temp, uri) = setup_codex_tree();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("turl"));
+    cmd.env("CODEX_HOME", temp.path())
+        .env("CLAUDE_CONFIG_DIR", temp.path().join("missing-claude"))
+        .arg(&uri)
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"session_id\""))
+        .stdout(predicate::str::contains("\"messages\""))
+        .stdout(predicate::str::contains("\"role\": \"user\""));
+}
+
+#[test]
+fn format_html_outputs_self_contained_document() {
+    let (temp, uri) = setup_codex_tree();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("turl"));
+    cmd.env("CODEX_HOME", temp.path())
+        .env("CLAUDE_CONFIG_DIR", temp.path().join("missing-claude"))
+        .arg(&uri)
+        .arg("--format")
+        .arg("html")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("turn-user"));
+}
+
 #[test]
 fn raw_outputs_json() {
     let (temp, uri) = setup_codex_tree();
@@ -51,6 +84,21 @@ fn raw_outputs_json() {
         .stdout(predicate::str::contains("\"response_item\""));
 }
 
+#[test]
+fn search_finds_matching_turn() {
+    let (temp, _uri) = setup_codex_tree();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("turl"));
+    cmd.env("CODEX_HOME", temp.path())
+        .env("CLAUDE_CONFIG_DIR", temp.path().join("missing-claude"))
+        .arg("search")
+        .arg("world")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("019c871c-b1f9-7f60-9c4f-87ed09f13592"))
+        .stdout(predicate::str::contains("world"));
+}
+
 #[test]
 fn missing_thread_returns_non_zero() {
     let temp = tempdir().expect("tempdir");
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, TurlError>;
+
+#[derive(Debug, Error)]
+pub enum TurlError {
+    #[error("invalid thread uri `{uri}`: {reason}")]
+    InvalidUri { uri: String, reason: String },
+
+    #[error("thread not found for provider={provider} session_id={session_id} (searched: {searched_roots:?})")]
+    ThreadNotFound {
+        provider: String,
+        session_id: String,
+        searched_roots: Vec<PathBuf>,
+    },
+
+    #[error("ambiguous session id prefix `{prefix}` for provider={provider}; matches: {candidates:?}")]
+    AmbiguousSessionId {
+        provider: String,
+        prefix: String,
+        candidates: Vec<String>,
+    },
+
+    #[error("invalid json on {path}:{line}: {source}")]
+    InvalidJsonLine {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to serialize thread at {path}: {source}")]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("invalid search query `{query}`: {reason}")]
+    InvalidQuery { query: String, reason: String },
+}
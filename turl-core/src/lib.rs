@@ -6,7 +6,11 @@ pub mod service;
 pub mod uri;
 
 pub use error::{Result, TurlError};
-pub use model::{MessageRole, ProviderKind, ResolutionMeta, ResolvedThread, ThreadMessage};
+pub use model::{MessageRole, ProviderKind, ResolutionMeta, ResolvedThread, SearchHit, ThreadMessage};
 pub use provider::ProviderRoots;
-pub use service::{read_thread_raw, render_thread_markdown, resolve_thread};
+pub use render::OutputFormat;
+pub use service::{
+    SearchOptions, SearchResults, read_thread_raw, render_thread, render_thread_markdown, resolve_thread,
+    search_threads,
+};
 pub use uri::ThreadUri;
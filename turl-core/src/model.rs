@@ -0,0 +1,59 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderKind {
+    Codex,
+    Claude,
+}
+
+impl fmt::Display for ProviderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProviderKind::Codex => "codex",
+            ProviderKind::Claude => "claude",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThreadMessage {
+    pub role: MessageRole,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionMeta {
+    pub source: String,
+    pub candidate_count: usize,
+    pub warnings: Vec<String>,
+    /// Whether this resolution reused an existing lookup structure (the
+    /// persisted session index, or an index or file listing kept in memory
+    /// from an earlier resolve) instead of a fresh directory scan.
+    pub from_cache: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedThread {
+    pub provider: ProviderKind,
+    pub session_id: String,
+    pub path: PathBuf,
+    pub metadata: ResolutionMeta,
+}
+
+/// A single match from `search::search_threads`.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub provider: ProviderKind,
+    pub session_id: String,
+    pub turn_index: usize,
+    pub role: MessageRole,
+    pub snippet: String,
+}
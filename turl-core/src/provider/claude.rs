@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use walkdir::WalkDir;
+
+use crate::error::{Result, TurlError};
+use crate::model::{ProviderKind, ResolutionMeta, ResolvedThread};
+use crate::provider::Provider;
+
+#[derive(Debug, Clone)]
+pub struct ClaudeProvider {
+    root: PathBuf,
+    files_cache: RefCell<Option<Vec<PathBuf>>>,
+}
+
+impl ClaudeProvider {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            files_cache: RefCell::new(None),
+        }
+    }
+
+    /// Drops the in-memory file listing so the next `resolve` re-walks
+    /// `projects`, picking up filesystem changes made since this provider
+    /// was created or last refreshed.
+    pub fn refresh(&self) {
+        *self.files_cache.borrow_mut() = None;
+    }
+
+    fn all_files(root: &Path) -> Vec<PathBuf> {
+        if !root.exists() {
+            return Vec::new();
+        }
+
+        WalkDir::new(root)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect()
+    }
+
+    /// Returns the cached `projects` file listing, walking the directory
+    /// only the first time it's needed (or after an explicit `refresh`).
+    fn cached_files(&self) -> Vec<PathBuf> {
+        if self.files_cache.borrow().is_none() {
+            let files = Self::all_files(&self.projects_root());
+            *self.files_cache.borrow_mut() = Some(files);
+        }
+
+        self.files_cache.borrow().as_ref().expect("populated above").clone()
+    }
+
+    fn projects_root(&self) -> PathBuf {
+        self.root.join("projects")
+    }
+
+    fn find_candidates(&self, session_id: &str) -> Vec<PathBuf> {
+        let needle = format!("{session_id}.jsonl");
+        self.cached_files()
+            .into_iter()
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.ends_with(&needle))
+            })
+            .collect()
+    }
+
+    fn choose_latest(paths: Vec<PathBuf>) -> Option<(PathBuf, usize)> {
+        if paths.is_empty() {
+            return None;
+        }
+
+        let mut scored = paths
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                (path, modified)
+            })
+            .collect::<Vec<_>>();
+
+        scored.sort_by_key(|(_, modified)| Reverse(*modified));
+        let count = scored.len();
+        scored.into_iter().next().map(|(path, _)| (path, count))
+    }
+}
+
+impl Provider for ClaudeProvider {
+    fn resolve(&self, session_id: &str) -> Result<ResolvedThread> {
+        let from_cache = self.files_cache.borrow().is_some();
+        let candidates = self.find_candidates(session_id);
+
+        if let Some((selected, count)) = Self::choose_latest(candidates) {
+            let mut meta = ResolutionMeta {
+                source: "claude:projects".to_string(),
+                candidate_count: count,
+                warnings: Vec::new(),
+                from_cache,
+            };
+            if count > 1 {
+                meta.warnings.push(format!(
+                    "multiple matches found ({count}) for session_id={session_id}; selected latest: {}",
+                    selected.display()
+                ));
+            }
+
+            return Ok(ResolvedThread {
+                provider: ProviderKind::Claude,
+                session_id: session_id.to_string(),
+                path: selected,
+                metadata: meta,
+            });
+        }
+
+        Err(TurlError::ThreadNotFound {
+            provider: ProviderKind::Claude.to_string(),
+            session_id: session_id.to_string(),
+            searched_roots: vec![self.projects_root()],
+        })
+    }
+}
@@ -1,22 +1,34 @@
+use std::cell::RefCell;
 use std::cmp::Reverse;
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::time::SystemTime;
-
-use walkdir::WalkDir;
+use std::path::PathBuf;
 
 use crate::error::{Result, TurlError};
 use crate::model::{ProviderKind, ResolutionMeta, ResolvedThread};
 use crate::provider::Provider;
+use crate::provider::index::{self, IndexEntry, SessionIndex};
+
+const SESSIONS_SOURCE: &str = "codex:sessions";
+const ARCHIVED_SOURCE: &str = "codex:archived_sessions";
 
 #[derive(Debug, Clone)]
 pub struct CodexProvider {
     root: PathBuf,
+    index_cache: RefCell<Option<SessionIndex>>,
 }
 
 impl CodexProvider {
     pub fn new(root: impl Into<PathBuf>) -> Self {
-        Self { root: root.into() }
+        Self {
+            root: root.into(),
+            index_cache: RefCell::new(None),
+        }
+    }
+
+    /// Drops the in-memory index so the next `resolve` re-derives it from
+    /// the on-disk cache (or a full scan), picking up filesystem changes
+    /// made since this provider was created or last refreshed.
+    pub fn refresh(&self) {
+        *self.index_cache.borrow_mut() = None;
     }
 
     fn sessions_root(&self) -> PathBuf {
@@ -27,43 +39,52 @@ impl CodexProvider {
         self.root.join("archived_sessions")
     }
 
-    fn find_candidates(root: &Path, session_id: &str) -> Vec<PathBuf> {
-        let needle = format!("{session_id}.jsonl");
-        if !root.exists() {
-            return Vec::new();
-        }
-
-        WalkDir::new(root)
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-            .filter(|entry| entry.file_type().is_file())
-            .map(|entry| entry.into_path())
-            .filter(|path| {
-                path.file_name()
-                    .and_then(|name| name.to_str())
-                    .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(&needle))
-            })
-            .collect()
-    }
-
-    fn choose_latest(paths: Vec<PathBuf>) -> Option<(PathBuf, usize)> {
-        if paths.is_empty() {
+    /// Picks the newest entry by mtime, for genuine duplicates sharing a
+    /// session id (e.g. retried rollouts).
+    fn choose_latest(mut entries: Vec<IndexEntry>) -> Option<(IndexEntry, usize)> {
+        if entries.is_empty() {
             return None;
         }
 
-        let mut scored = paths
-            .into_iter()
-            .map(|path| {
-                let modified = fs::metadata(&path)
-                    .and_then(|meta| meta.modified())
-                    .unwrap_or(SystemTime::UNIX_EPOCH);
-                (path, modified)
-            })
-            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| Reverse(entry.modified));
+        let count = entries.len();
+        entries.into_iter().next().map(|entry| (entry, count))
+    }
 
-        scored.sort_by_key(|(_, modified)| Reverse(*modified));
-        let count = scored.len();
-        scored.into_iter().next().map(|(path, _)| (path, count))
+    /// Prefers `SESSIONS_SOURCE` entries over `ARCHIVED_SOURCE` ones,
+    /// falling back to archived only when nothing is active. This keeps an
+    /// archived/superseded copy from shadowing a live session just because
+    /// its mtime happens to be newer, and keeps `choose_latest`'s
+    /// multiple-matches warning scoped to genuine same-tree duplicates.
+    fn active_entries(entries: Vec<IndexEntry>) -> Vec<IndexEntry> {
+        let (sessions, archived): (Vec<_>, Vec<_>) =
+            entries.into_iter().partition(|entry| entry.source == SESSIONS_SOURCE);
+        if sessions.is_empty() { archived } else { sessions }
+    }
+
+    fn resolve_from_entries(full_session_id: &str, entries: Vec<IndexEntry>, from_cache: bool) -> ResolvedThread {
+        let entries = Self::active_entries(entries);
+        let (chosen, count) = Self::choose_latest(entries).expect("caller guarantees non-empty entries");
+
+        let mut metadata = ResolutionMeta {
+            source: chosen.source.clone(),
+            candidate_count: count,
+            warnings: Vec::new(),
+            from_cache,
+        };
+        if count > 1 {
+            metadata.warnings.push(format!(
+                "multiple matches found ({count}) for session_id={full_session_id}; selected latest: {}",
+                chosen.path.display()
+            ));
+        }
+
+        ResolvedThread {
+            provider: ProviderKind::Codex,
+            session_id: full_session_id.to_string(),
+            path: chosen.path,
+            metadata,
+        }
     }
 }
 
@@ -72,48 +93,46 @@ impl Provider for CodexProvider {
         let sessions = self.sessions_root();
         let archived = self.archived_root();
 
-        let active_candidates = Self::find_candidates(&sessions, session_id);
-        if let Some((selected, count)) = Self::choose_latest(active_candidates) {
-            let mut meta = ResolutionMeta {
-                source: "codex:sessions".to_string(),
-                candidate_count: count,
-                warnings: Vec::new(),
-            };
-            if count > 1 {
-                meta.warnings.push(format!(
-                    "multiple matches found ({count}) for session_id={session_id}; selected latest: {}",
-                    selected.display()
-                ));
+        // Once loaded, the index is kept in memory for the rest of this
+        // provider's lifetime and reused as-is; only `refresh` forces the
+        // next resolve to hit disk/WalkDir again.
+        let from_cache = if self.index_cache.borrow().is_some() {
+            true
+        } else {
+            let roots = [(SESSIONS_SOURCE, sessions.clone()), (ARCHIVED_SOURCE, archived.clone())];
+            let (index, from_disk_cache) = SessionIndex::load_or_build(&self.root, &roots);
+            if !from_disk_cache {
+                index.persist(&self.root);
             }
+            *self.index_cache.borrow_mut() = Some(index);
+            from_disk_cache
+        };
+
+        let index_ref = self.index_cache.borrow();
+        let index = index_ref.as_ref().expect("populated above");
 
-            return Ok(ResolvedThread {
-                provider: ProviderKind::Codex,
-                session_id: session_id.to_string(),
-                path: selected,
-                metadata: meta,
-            });
+        if let Some(entries) = index.exact(session_id) {
+            return Ok(Self::resolve_from_entries(session_id, entries.to_vec(), from_cache));
         }
 
-        let archived_candidates = Self::find_candidates(&archived, session_id);
-        if let Some((selected, count)) = Self::choose_latest(archived_candidates) {
-            let mut meta = ResolutionMeta {
-                source: "codex:archived_sessions".to_string(),
-                candidate_count: count,
-                warnings: Vec::new(),
-            };
-            if count > 1 {
-                meta.warnings.push(format!(
-                    "multiple archived matches found ({count}) for session_id={session_id}; selected latest: {}",
-                    selected.display()
-                ));
+        if index::is_candidate_prefix(session_id) {
+            let matches = index.by_prefix(session_id);
+            match matches.len() {
+                0 => {}
+                1 => {
+                    let (full_id, entries) = matches[0];
+                    return Ok(Self::resolve_from_entries(full_id, entries.to_vec(), from_cache));
+                }
+                _ => {
+                    let mut candidates: Vec<String> = matches.into_iter().map(|(id, _)| id.to_string()).collect();
+                    candidates.sort();
+                    return Err(TurlError::AmbiguousSessionId {
+                        provider: ProviderKind::Codex.to_string(),
+                        prefix: session_id.to_string(),
+                        candidates,
+                    });
+                }
             }
-
-            return Ok(ResolvedThread {
-                provider: ProviderKind::Codex,
-                session_id: session_id.to_string(),
-                path: selected,
-                metadata: meta,
-            });
         }
 
         Err(TurlError::ThreadNotFound {
@@ -147,6 +166,7 @@ mod tests {
             .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
             .expect("resolve should succeed");
         assert_eq!(resolved.path, path);
+        assert!(!resolved.metadata.from_cache);
     }
 
     #[test]
@@ -175,4 +195,182 @@ mod tests {
             .expect_err("should fail");
         assert!(format!("{err}").contains("thread not found"));
     }
+
+    #[test]
+    fn resolves_by_unique_prefix() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp
+            .path()
+            .join("sessions/2026/02/23/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        let resolved = provider.resolve("019c871c").expect("resolve should succeed");
+        assert_eq!(resolved.path, path);
+        assert_eq!(resolved.session_id, "019c871c-b1f9-7f60-9c4f-87ed09f13592");
+    }
+
+    #[test]
+    fn resolves_by_unique_prefix_spanning_a_uuid_dash() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp
+            .path()
+            .join("sessions/2026/02/23/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        // 12 hex chars, crossing the dash between the UUID's first and
+        // second groups ("019c871c-b1f9" with the dash removed).
+        let resolved = provider.resolve("019c871cb1f9").expect("resolve should succeed");
+        assert_eq!(resolved.path, path);
+        assert_eq!(resolved.session_id, "019c871c-b1f9-7f60-9c4f-87ed09f13592");
+    }
+
+    #[test]
+    fn returns_ambiguous_error_for_shared_prefix() {
+        let temp = tempdir().expect("tempdir");
+        let first = temp
+            .path()
+            .join("sessions/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        let second = temp
+            .path()
+            .join("sessions/rollout-2026-02-23T05-10-12-019c871c-aaaa-7f60-9c4f-87ed09f13593.jsonl");
+        fs::create_dir_all(first.parent().expect("parent")).expect("mkdir");
+        fs::write(&first, "{}\n").expect("write");
+        fs::write(&second, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        let err = provider.resolve("019c871c").unwrap_err();
+        assert!(format!("{err}").contains("ambiguous"));
+    }
+
+    #[test]
+    fn second_resolve_is_served_from_cache() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp
+            .path()
+            .join("sessions/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        let first = provider
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect("resolve should succeed");
+        assert!(!first.metadata.from_cache);
+
+        let second = provider
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect("resolve should succeed");
+        assert!(second.metadata.from_cache);
+    }
+
+    #[test]
+    fn prefers_active_session_over_newer_archived_duplicate() {
+        let temp = tempdir().expect("tempdir");
+        let active_path = temp
+            .path()
+            .join("sessions/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        let archived_path = temp
+            .path()
+            .join("archived_sessions/rollout-2026-02-22T01-05-36-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(active_path.parent().expect("parent")).expect("mkdir");
+        fs::create_dir_all(archived_path.parent().expect("parent")).expect("mkdir");
+        // Write the active copy first so the archived duplicate, written
+        // after, ends up with the strictly newer mtime — yet the active
+        // tree must still win.
+        fs::write(&active_path, "{}\n").expect("write");
+        fs::write(&archived_path, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        let resolved = provider
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect("resolve should succeed");
+        assert_eq!(resolved.path, active_path);
+        assert_eq!(resolved.metadata.source, "codex:sessions");
+    }
+
+    #[test]
+    fn ignores_non_rollout_files_with_uuid_shaped_names() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp
+            .path()
+            .join("sessions/2026/02/23/backup-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, "{}\n").expect("write");
+
+        let provider = CodexProvider::new(temp.path());
+        let err = provider
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect_err("non-rollout file should not be indexed as a session");
+        assert!(format!("{err}").contains("thread not found"));
+    }
+
+    #[test]
+    fn detects_new_session_added_under_an_existing_date_directory_across_processes() {
+        let temp = tempdir().expect("tempdir");
+        let first_path = temp
+            .path()
+            .join("sessions/2026/02/23/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(first_path.parent().expect("parent")).expect("mkdir");
+        fs::write(&first_path, "{}\n").expect("write");
+
+        // Simulates one process (or CLI invocation) building and persisting
+        // the on-disk cache, then a later one picking it back up.
+        let first_process = CodexProvider::new(temp.path());
+        first_process
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect("first resolve should succeed and persist the cache");
+
+        // A sibling day directory under the same already-indexed `02` month
+        // directory only bumps the mtimes of `02` and the new `24` directory,
+        // not `sessions`'s — the disk cache must still notice the new session.
+        let second_path = temp
+            .path()
+            .join("sessions/2026/02/24/rollout-2026-02-24T09-00-00-019c8129-f668-7951-8d56-cc5513541c26.jsonl");
+        fs::create_dir_all(second_path.parent().expect("parent")).expect("mkdir");
+        fs::write(&second_path, "{}\n").expect("write");
+
+        let second_process = CodexProvider::new(temp.path());
+        let resolved = second_process
+            .resolve("019c8129-f668-7951-8d56-cc5513541c26")
+            .expect("a fresh provider should find the newly added session");
+        assert_eq!(resolved.path, second_path);
+    }
+
+    #[test]
+    fn refresh_picks_up_sessions_added_after_the_first_resolve() {
+        let temp = tempdir().expect("tempdir");
+        let first_path = temp
+            .path()
+            .join("sessions/2026/02/23/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl");
+        fs::create_dir_all(first_path.parent().expect("parent")).expect("mkdir");
+        fs::write(&first_path, "{}\n").expect("write");
+
+        // A single long-lived provider, as the server's `main_loop` holds
+        // one across many requests.
+        let provider = CodexProvider::new(temp.path());
+        provider
+            .resolve("019c871c-b1f9-7f60-9c4f-87ed09f13592")
+            .expect("first resolve should succeed");
+
+        let second_path = temp
+            .path()
+            .join("sessions/2026/02/24/rollout-2026-02-24T09-00-00-019c8129-f668-7951-8d56-cc5513541c26.jsonl");
+        fs::create_dir_all(second_path.parent().expect("parent")).expect("mkdir");
+        fs::write(&second_path, "{}\n").expect("write");
+
+        let err = provider
+            .resolve("019c8129-f668-7951-8d56-cc5513541c26")
+            .expect_err("without a refresh, the in-memory index shouldn't see the new file");
+        assert!(format!("{err}").contains("thread not found"));
+
+        provider.refresh();
+        let resolved = provider
+            .resolve("019c8129-f668-7951-8d56-cc5513541c26")
+            .expect("after a refresh, the new session should resolve");
+        assert_eq!(resolved.path, second_path);
+    }
 }
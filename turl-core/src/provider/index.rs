@@ -0,0 +1,178 @@
+//! A session index that maps session ids to candidate file paths, so a
+//! resolve doesn't have to re-walk the whole provider tree every time.
+//!
+//! The index is persisted to a cache file under the provider root and is
+//! rebuilt whenever any directory under a scanned root has a newer mtime
+//! than when the cache was written. A new file only bumps the mtime of its
+//! immediate parent directory, not every ancestor up to the root, so
+//! freshness is decided from the newest mtime across the *whole* directory
+//! tree rather than just the root directory itself.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+const INDEX_FILE_NAME: &str = ".turl-session-index.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SessionIndex {
+    root_modified: HashMap<PathBuf, SystemTime>,
+    by_session_id: HashMap<String, Vec<IndexEntry>>,
+}
+
+impl SessionIndex {
+    /// Loads the cached index for `provider_root` if it is still fresh with
+    /// respect to `roots`, otherwise rebuilds it from a full scan. Returns
+    /// the index alongside whether it came from the cache.
+    pub fn load_or_build(provider_root: &Path, roots: &[(&str, PathBuf)]) -> (Self, bool) {
+        if let Some(cached) = Self::load(provider_root)
+            && cached.is_fresh(roots)
+        {
+            return (cached, true);
+        }
+
+        (Self::build(roots), false)
+    }
+
+    pub fn persist(&self, provider_root: &Path) {
+        if let Ok(data) = serde_json::to_string(self) {
+            let _ = fs::write(Self::cache_path(provider_root), data);
+        }
+    }
+
+    pub fn exact(&self, session_id: &str) -> Option<&[IndexEntry]> {
+        self.by_session_id.get(session_id).map(Vec::as_slice)
+    }
+
+    /// Returns every (full session id, entries) pair whose id, with its
+    /// UUID dashes removed, starts with `prefix`. `prefix` is expected to be
+    /// a run of plain hex digits (see `is_candidate_prefix`), but a stored
+    /// id is `8-4-4-4-12` dashed, so the dashes have to come out before the
+    /// comparison or any prefix past the first 8 chars can never match.
+    pub fn by_prefix<'a>(&'a self, prefix: &str) -> Vec<(&'a str, &'a [IndexEntry])> {
+        self.by_session_id
+            .iter()
+            .filter(|(id, _)| strip_dashes(id).starts_with(prefix))
+            .map(|(id, entries)| (id.as_str(), entries.as_slice()))
+            .collect()
+    }
+
+    fn cache_path(provider_root: &Path) -> PathBuf {
+        provider_root.join(INDEX_FILE_NAME)
+    }
+
+    fn load(provider_root: &Path) -> Option<Self> {
+        let data = fs::read_to_string(Self::cache_path(provider_root)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn is_fresh(&self, roots: &[(&str, PathBuf)]) -> bool {
+        roots.iter().all(|(_, root)| {
+            self.root_modified.get(root).copied() == tree_fingerprint(root)
+        })
+    }
+
+    fn build(roots: &[(&str, PathBuf)]) -> Self {
+        let mut root_modified = HashMap::new();
+        let mut by_session_id: HashMap<String, Vec<IndexEntry>> = HashMap::new();
+
+        for (label, root) in roots {
+            if let Some(fingerprint) = tree_fingerprint(root) {
+                root_modified.insert(root.clone(), fingerprint);
+            }
+
+            if !root.exists() {
+                continue;
+            }
+
+            for entry in WalkDir::new(root)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+            {
+                let path = entry.into_path();
+                let Some(session_id) = session_id_from_filename(&path) else {
+                    continue;
+                };
+                let modified = fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+
+                by_session_id.entry(session_id).or_default().push(IndexEntry {
+                    path,
+                    modified,
+                    source: (*label).to_string(),
+                });
+            }
+        }
+
+        Self {
+            root_modified,
+            by_session_id,
+        }
+    }
+}
+
+/// The newest mtime across every directory in `root`'s subtree (including
+/// `root` itself). Adding a file anywhere in the tree bumps at least its
+/// immediate parent's mtime, so this changes whenever the tree does, even
+/// though no single directory's mtime alone is guaranteed to.
+fn tree_fingerprint(root: &Path) -> Option<SystemTime> {
+    if !root.exists() {
+        return None;
+    }
+
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| fs::metadata(entry.path()).and_then(|meta| meta.modified()).ok())
+        .max()
+}
+
+/// Extracts the trailing UUID from a rollout file's stem, e.g.
+/// `rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592` ->
+/// `019c871c-b1f9-7f60-9c4f-87ed09f13592`. Only `rollout-`-prefixed names are
+/// considered, so stray non-rollout files that happen to end in a UUID-shaped
+/// string aren't indexed as sessions.
+pub(crate) fn session_id_from_filename(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    if !stem.starts_with("rollout-") || stem.len() < 36 {
+        return None;
+    }
+
+    let candidate = &stem[stem.len() - 36..];
+    is_uuid(candidate).then(|| candidate.to_string())
+}
+
+fn strip_dashes(id: &str) -> String {
+    id.chars().filter(|c| *c != '-').collect()
+}
+
+fn is_uuid(candidate: &str) -> bool {
+    const GROUP_LENGTHS: [usize; 5] = [8, 4, 4, 4, 12];
+    let groups: Vec<&str> = candidate.split('-').collect();
+
+    groups.len() == GROUP_LENGTHS.len()
+        && groups
+            .iter()
+            .zip(GROUP_LENGTHS)
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// A session id is eligible for prefix matching when it is a run of hex
+/// digits shorter than a full UUID (36 chars) but at least 8 of them.
+pub fn is_candidate_prefix(session_id: &str) -> bool {
+    (8..36).contains(&session_id.len()) && session_id.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -0,0 +1,70 @@
+use std::env;
+use std::path::PathBuf;
+
+pub mod claude;
+pub mod codex;
+pub mod index;
+
+pub use claude::ClaudeProvider;
+pub use codex::CodexProvider;
+
+use crate::error::Result;
+use crate::model::ResolvedThread;
+
+pub trait Provider {
+    fn resolve(&self, session_id: &str) -> Result<ResolvedThread>;
+}
+
+/// The provider roots discovered from the environment, shared by the CLI
+/// and the JSON-RPC server so both scan the same directories.
+///
+/// `codex()`/`claude()` hand back the same long-lived provider on every
+/// call rather than building a fresh one, so a process that resolves many
+/// threads (the server's `main_loop`) reuses each provider's in-memory
+/// index across the whole process lifetime instead of re-deriving it from
+/// disk on every resolve. Call `CodexProvider::refresh`/`ClaudeProvider::refresh`
+/// explicitly when a caller actually needs to see filesystem changes made
+/// since the provider was created.
+#[derive(Debug)]
+pub struct ProviderRoots {
+    pub codex_home: PathBuf,
+    pub claude_config_dir: PathBuf,
+    codex: CodexProvider,
+    claude: ClaudeProvider,
+}
+
+impl ProviderRoots {
+    pub fn new(codex_home: impl Into<PathBuf>, claude_config_dir: impl Into<PathBuf>) -> Self {
+        let codex_home = codex_home.into();
+        let claude_config_dir = claude_config_dir.into();
+        Self {
+            codex: CodexProvider::new(codex_home.clone()),
+            claude: ClaudeProvider::new(claude_config_dir.clone()),
+            codex_home,
+            claude_config_dir,
+        }
+    }
+
+    pub fn discover() -> Self {
+        let codex_home = env::var_os("CODEX_HOME").map(PathBuf::from).unwrap_or_else(|| home_dir_join(".codex"));
+        let claude_config_dir = env::var_os("CLAUDE_CONFIG_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home_dir_join(".claude"));
+        Self::new(codex_home, claude_config_dir)
+    }
+
+    pub fn codex(&self) -> &CodexProvider {
+        &self.codex
+    }
+
+    pub fn claude(&self) -> &ClaudeProvider {
+        &self.claude
+    }
+}
+
+fn home_dir_join(suffix: &str) -> PathBuf {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(suffix)
+}
@@ -1,11 +1,30 @@
 use std::path::Path;
 
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use crate::error::{Result, TurlError};
-use crate::model::{MessageRole, ProviderKind, ThreadMessage};
+use crate::model::{MessageRole, ProviderKind, ResolvedThread, ThreadMessage};
 use crate::uri::ThreadUri;
 
+/// The external encodings a resolved thread can be rendered into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl OutputFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
 const TOOL_TYPES: &[&str] = &[
     "tool_call",
     "tool_result",
@@ -42,6 +61,82 @@ pub fn render_markdown(uri: &ThreadUri, source_path: &Path, raw_jsonl: &str) ->
     Ok(output)
 }
 
+/// Serializes a resolved thread to the documented JSON schema:
+///
+/// ```json
+/// {
+///   "uri": "codex://...",
+///   "provider": "codex",
+///   "session_id": "...",
+///   "source_path": "...",
+///   "warnings": ["..."],
+///   "messages": [{"role": "user", "text": "..."}]
+/// }
+/// ```
+pub fn render_json(uri: &ThreadUri, resolved: &ResolvedThread, raw_jsonl: &str) -> Result<String> {
+    let messages = extract_messages(uri.provider, &resolved.path, raw_jsonl)?;
+
+    let messages = messages
+        .iter()
+        .map(|message| {
+            let role = match message.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            };
+            json!({ "role": role, "text": message.text })
+        })
+        .collect::<Vec<_>>();
+
+    let document = json!({
+        "uri": uri.as_string(),
+        "provider": resolved.provider.to_string(),
+        "session_id": resolved.session_id,
+        "source_path": resolved.path.display().to_string(),
+        "warnings": resolved.metadata.warnings,
+        "messages": messages,
+    });
+
+    serde_json::to_string_pretty(&document).map_err(|source| TurlError::Serialize {
+        path: resolved.path.clone(),
+        source,
+    })
+}
+
+/// Renders a self-contained HTML document with one `<section>` per turn.
+pub fn render_html(uri: &ThreadUri, resolved: &ResolvedThread, raw_jsonl: &str) -> Result<String> {
+    let messages = extract_messages(uri.provider, &resolved.path, raw_jsonl)?;
+
+    let mut body = String::new();
+    for (idx, message) in messages.iter().enumerate() {
+        let role = match message.role {
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+        };
+
+        body.push_str(&format!(
+            "<section class=\"turn turn-{role}\">\n  <h2>{}. {}</h2>\n  <pre>{}</pre>\n</section>\n",
+            idx + 1,
+            role,
+            html_escape(message.text.trim())
+        ));
+    }
+
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"utf-8\">\n  <title>{}</title>\n</head>\n<body>\n<h1>Thread</h1>\n<p>URI: <code>{}</code></p>\n<p>Source: <code>{}</code></p>\n{}</body>\n</html>\n",
+        html_escape(&uri.as_string()),
+        html_escape(&uri.as_string()),
+        html_escape(&resolved.path.display().to_string()),
+        body,
+    ))
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 pub fn extract_messages(
     provider: ProviderKind,
     path: &Path,
@@ -201,10 +296,11 @@ fn extract_text(content: Option<&Value>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
-    use crate::model::ProviderKind;
-    use crate::render::extract_messages;
+    use crate::model::{ProviderKind, ResolutionMeta, ResolvedThread};
+    use crate::render::{extract_messages, render_json};
+    use crate::uri::ThreadUri;
 
     #[test]
     fn codex_filters_function_calls() {
@@ -229,4 +325,27 @@ mod tests {
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[1].text, "done");
     }
+
+    #[test]
+    fn render_json_includes_metadata_and_messages() {
+        let raw = r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hello"}]}}"#;
+        let path = PathBuf::from("/tmp/mock.jsonl");
+        let uri = ThreadUri::parse("codex://019c871c-b1f9-7f60-9c4f-87ed09f13592").expect("parse");
+        let resolved = ResolvedThread {
+            provider: ProviderKind::Codex,
+            session_id: "019c871c-b1f9-7f60-9c4f-87ed09f13592".to_string(),
+            path: path.clone(),
+            metadata: ResolutionMeta {
+                source: "codex:sessions".to_string(),
+                candidate_count: 1,
+                warnings: Vec::new(),
+                from_cache: false,
+            },
+        };
+
+        let json = render_json(&uri, &resolved, raw).expect("render_json");
+        assert!(json.contains("\"session_id\": \"019c871c-b1f9-7f60-9c4f-87ed09f13592\""));
+        assert!(json.contains("\"role\": \"user\""));
+        assert!(json.contains("\"text\": \"hello\""));
+    }
 }
@@ -0,0 +1,51 @@
+pub mod server;
+
+mod search;
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Result, TurlError};
+use crate::model::{ProviderKind, ResolvedThread};
+use crate::provider::{Provider, ProviderRoots};
+use crate::render::{self, OutputFormat};
+use crate::uri::ThreadUri;
+
+pub use search::{SearchOptions, SearchResults, search_threads};
+
+pub fn resolve_thread(uri: &ThreadUri, roots: &ProviderRoots) -> Result<ResolvedThread> {
+    match uri.provider {
+        ProviderKind::Codex => roots.codex().resolve(&uri.session_id),
+        ProviderKind::Claude => roots.claude().resolve(&uri.session_id),
+    }
+}
+
+pub fn read_thread_raw(uri: &ThreadUri, roots: &ProviderRoots) -> Result<String> {
+    let resolved = resolve_thread(uri, roots)?;
+    read_to_string(&resolved.path)
+}
+
+pub fn render_thread_markdown(uri: &ThreadUri, roots: &ProviderRoots) -> Result<String> {
+    let resolved = resolve_thread(uri, roots)?;
+    let raw = read_to_string(&resolved.path)?;
+    render::render_markdown(uri, &resolved.path, &raw)
+}
+
+/// Resolves and renders a thread in the requested `OutputFormat`.
+pub fn render_thread(uri: &ThreadUri, format: OutputFormat, roots: &ProviderRoots) -> Result<String> {
+    let resolved = resolve_thread(uri, roots)?;
+    let raw = read_to_string(&resolved.path)?;
+
+    match format {
+        OutputFormat::Markdown => render::render_markdown(uri, &resolved.path, &raw),
+        OutputFormat::Json => render::render_json(uri, &resolved, &raw),
+        OutputFormat::Html => render::render_html(uri, &resolved, &raw),
+    }
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| TurlError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
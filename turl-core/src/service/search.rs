@@ -0,0 +1,266 @@
+//! Full-text search across every session reachable from a `ProviderRoots`,
+//! layered on top of the same `render::extract_messages` the providers use
+//! to render a single thread.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::error::{Result, TurlError};
+use crate::model::{MessageRole, ProviderKind, SearchHit};
+use crate::provider::ProviderRoots;
+use crate::provider::index;
+use crate::render::extract_messages;
+
+const DEFAULT_SNIPPET_RADIUS: usize = 80;
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub provider: Option<ProviderKind>,
+    pub role: Option<MessageRole>,
+    pub regex: bool,
+    /// Bytes of context kept on each side of a match, widened to the nearest
+    /// UTF-8 character boundary. `0` uses the default.
+    pub snippet_radius: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    /// Paths that failed to read or parse and were skipped rather than
+    /// aborting the whole search.
+    pub warnings: Vec<PathBuf>,
+}
+
+enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn compile(query: &str, use_regex: bool) -> Result<Self> {
+        if use_regex {
+            Regex::new(query)
+                .map(Matcher::Regex)
+                .map_err(|source| TurlError::InvalidQuery {
+                    query: query.to_string(),
+                    reason: source.to_string(),
+                })
+        } else {
+            Ok(Matcher::Substring(query.to_string()))
+        }
+    }
+
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        match self {
+            Matcher::Substring(needle) => text.find(needle.as_str()).map(|start| (start, start + needle.len())),
+            Matcher::Regex(regex) => regex.find(text).map(|m| (m.start(), m.end())),
+        }
+    }
+}
+
+/// Scans every session under `roots` for `query`, running the per-file
+/// extraction across a small pool of threads. Files that fail to read or
+/// parse are skipped and recorded in the result's `warnings`.
+pub fn search_threads(query: &str, opts: &SearchOptions, roots: &ProviderRoots) -> Result<SearchResults> {
+    let matcher = Matcher::compile(query, opts.regex)?;
+    let files = collect_files(roots, opts.provider);
+    let snippet_radius = if opts.snippet_radius == 0 {
+        DEFAULT_SNIPPET_RADIUS
+    } else {
+        opts.snippet_radius
+    };
+
+    let worker_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(files.len().max(1));
+    let chunks = partition(files, worker_count);
+
+    let partials = thread::scope(|scope| {
+        chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(|| search_chunk(chunk, &matcher, opts.role, snippet_radius)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().expect("search worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    let mut combined = SearchResults::default();
+    for partial in partials {
+        combined.hits.extend(partial.hits);
+        combined.warnings.extend(partial.warnings);
+    }
+    Ok(combined)
+}
+
+fn search_chunk(
+    files: Vec<(ProviderKind, PathBuf)>,
+    matcher: &Matcher,
+    role_filter: Option<MessageRole>,
+    snippet_radius: usize,
+) -> SearchResults {
+    let mut results = SearchResults::default();
+
+    for (provider, path) in files {
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(_) => {
+                results.warnings.push(path);
+                continue;
+            }
+        };
+
+        let messages = match extract_messages(provider, &path, &raw) {
+            Ok(messages) => messages,
+            Err(_) => {
+                results.warnings.push(path);
+                continue;
+            }
+        };
+
+        let session_id = index::session_id_from_filename(&path)
+            .unwrap_or_else(|| path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("unknown").to_string());
+
+        for (turn_index, message) in messages.iter().enumerate() {
+            if role_filter.is_some_and(|role| role != message.role) {
+                continue;
+            }
+
+            if let Some((start, end)) = matcher.find(&message.text) {
+                results.hits.push(SearchHit {
+                    provider,
+                    session_id: session_id.clone(),
+                    turn_index,
+                    role: message.role,
+                    snippet: snippet(&message.text, start, end, snippet_radius),
+                });
+            }
+        }
+    }
+
+    results
+}
+
+fn collect_files(roots: &ProviderRoots, provider_filter: Option<ProviderKind>) -> Vec<(ProviderKind, PathBuf)> {
+    let mut files = Vec::new();
+
+    if provider_filter.is_none_or(|provider| provider == ProviderKind::Codex) {
+        for root in [roots.codex_home.join("sessions"), roots.codex_home.join("archived_sessions")] {
+            files.extend(walk_jsonl(&root).into_iter().map(|path| (ProviderKind::Codex, path)));
+        }
+    }
+
+    if provider_filter.is_none_or(|provider| provider == ProviderKind::Claude) {
+        let root = roots.claude_config_dir.join("projects");
+        files.extend(walk_jsonl(&root).into_iter().map(|path| (ProviderKind::Claude, path)));
+    }
+
+    files
+}
+
+fn walk_jsonl(root: &Path) -> Vec<PathBuf> {
+    if !root.exists() {
+        return Vec::new();
+    }
+
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("jsonl"))
+        .collect()
+}
+
+fn partition<T>(items: Vec<T>, worker_count: usize) -> Vec<Vec<T>> {
+    if worker_count <= 1 {
+        return vec![items];
+    }
+
+    let chunk_size = items.len().div_ceil(worker_count).max(1);
+    let mut chunks = Vec::new();
+    let mut remaining = items;
+
+    while !remaining.is_empty() {
+        let tail = remaining.split_off(chunk_size.min(remaining.len()));
+        chunks.push(remaining);
+        remaining = tail;
+    }
+
+    chunks
+}
+
+fn snippet(text: &str, start: usize, end: usize, radius: usize) -> String {
+    let from = floor_char_boundary(text, start.saturating_sub(radius));
+    let to = ceil_char_boundary(text, (end + radius).min(text.len()));
+    text[from..to].trim().to_string()
+}
+
+fn floor_char_boundary(text: &str, mut idx: usize) -> usize {
+    while idx > 0 && !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+fn ceil_char_boundary(text: &str, mut idx: usize) -> usize {
+    while idx < text.len() && !text.is_char_boundary(idx) {
+        idx += 1;
+    }
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn write_session(root: &Path, relative: &str, contents: &str) -> PathBuf {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, contents).expect("write");
+        path
+    }
+
+    #[test]
+    fn finds_substring_match_with_snippet() {
+        let temp = tempdir().expect("tempdir");
+        write_session(
+            temp.path(),
+            "sessions/rollout-2026-02-23T04-48-50-019c871c-b1f9-7f60-9c4f-87ed09f13592.jsonl",
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"please look at the NullPointerException in parser.rs"}]}}"#,
+        );
+
+        let roots = ProviderRoots::new(temp.path(), temp.path().join("missing-claude"));
+
+        let results = search_threads("NullPointerException", &SearchOptions::default(), &roots).expect("search");
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(results.hits[0].session_id, "019c871c-b1f9-7f60-9c4f-87ed09f13592");
+        assert!(results.hits[0].snippet.contains("NullPointerException"));
+        assert!(results.warnings.is_empty());
+    }
+
+    #[test]
+    fn skips_unparseable_files_and_records_warning() {
+        let temp = tempdir().expect("tempdir");
+        let bad = write_session(
+            temp.path(),
+            "sessions/rollout-2026-02-23T04-48-50-019c8129-f668-7951-8d56-cc5513541c26.jsonl",
+            "not json\n",
+        );
+
+        let roots = ProviderRoots::new(temp.path(), temp.path().join("missing-claude"));
+
+        let results = search_threads("anything", &SearchOptions::default(), &roots).expect("search");
+        assert!(results.hits.is_empty());
+        assert_eq!(results.warnings, vec![bad]);
+    }
+}
@@ -0,0 +1,185 @@
+//! A persistent JSON-RPC-over-stdio server, for hosts (e.g. an editor
+//! plugin) that want to resolve and render many threads without re-walking
+//! the provider trees on every invocation.
+//!
+//! The wire format is newline-delimited JSON: one request object per line
+//! on stdin, one response object per line on stdout. Each request carries
+//! an `id`, which is echoed back on the matching response alongside either
+//! a `result` or an `error`.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::TurlError;
+use crate::model::ResolvedThread;
+use crate::provider::ProviderRoots;
+use crate::service::{read_thread_raw, render_thread_markdown, resolve_thread};
+use crate::uri::ThreadUri;
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_PARAMS: i32 = -32602;
+const METHOD_NOT_FOUND: i32 = -32601;
+
+// Server-defined errors, one per `TurlError` variant other than `InvalidUri`
+// (which maps to `INVALID_PARAMS`), so a host can branch on `code` instead of
+// string-matching `message`.
+const THREAD_NOT_FOUND: i32 = -32001;
+const AMBIGUOUS_SESSION_ID: i32 = -32002;
+const INVALID_JSON_LINE: i32 = -32003;
+const IO_ERROR: i32 = -32004;
+const SERIALIZE_ERROR: i32 = -32005;
+const INVALID_QUERY: i32 = -32006;
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UriParams {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ResolvedThreadDto {
+    provider: String,
+    session_id: String,
+    source_path: String,
+    source: String,
+    candidate_count: usize,
+    from_cache: bool,
+    warnings: Vec<String>,
+}
+
+impl From<ResolvedThread> for ResolvedThreadDto {
+    fn from(resolved: ResolvedThread) -> Self {
+        Self {
+            provider: resolved.provider.to_string(),
+            session_id: resolved.session_id,
+            source_path: resolved.path.display().to_string(),
+            source: resolved.metadata.source,
+            candidate_count: resolved.metadata.candidate_count,
+            from_cache: resolved.metadata.from_cache,
+            warnings: resolved.metadata.warnings,
+        }
+    }
+}
+
+/// Reads one JSON-RPC request per line from stdin, dispatches it
+/// synchronously, and writes one response line to stdout, flushing after
+/// each. Returns once stdin is closed.
+pub fn main_loop() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let roots = ProviderRoots::discover();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) => dispatch(&request, &roots),
+            Err(source) => RpcResponse {
+                id: Value::Null,
+                result: None,
+                error: Some(RpcError {
+                    code: PARSE_ERROR,
+                    message: format!("invalid request: {source}"),
+                }),
+            },
+        };
+
+        serde_json::to_writer(&mut stdout, &response)?;
+        stdout.write_all(b"\n")?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+fn dispatch(request: &RpcRequest, roots: &ProviderRoots) -> RpcResponse {
+    let id = request.id.clone();
+
+    let outcome = match request.method.as_str() {
+        "resolve" => uri_params(request).and_then(|uri| {
+            let resolved = resolve_thread(&uri, roots).map_err(to_rpc_error)?;
+            to_value(ResolvedThreadDto::from(resolved))
+        }),
+        "renderMarkdown" => {
+            uri_params(request).and_then(|uri| render_thread_markdown(&uri, roots).map_err(to_rpc_error).map(Value::from))
+        }
+        "readRaw" => uri_params(request).and_then(|uri| read_thread_raw(&uri, roots).map_err(to_rpc_error).map(Value::from)),
+        other => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("unknown method `{other}`"),
+        }),
+    };
+
+    match outcome {
+        Ok(result) => RpcResponse {
+            id,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => RpcResponse {
+            id,
+            result: None,
+            error: Some(error),
+        },
+    }
+}
+
+fn uri_params(request: &RpcRequest) -> Result<ThreadUri, RpcError> {
+    let params: UriParams = serde_json::from_value(request.params.clone()).map_err(|source| RpcError {
+        code: INVALID_PARAMS,
+        message: format!("invalid params: {source}"),
+    })?;
+
+    ThreadUri::parse(&params.uri).map_err(to_rpc_error)
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|source| RpcError {
+        code: SERIALIZE_ERROR,
+        message: source.to_string(),
+    })
+}
+
+fn to_rpc_error(error: TurlError) -> RpcError {
+    let code = match &error {
+        TurlError::InvalidUri { .. } => INVALID_PARAMS,
+        TurlError::ThreadNotFound { .. } => THREAD_NOT_FOUND,
+        TurlError::AmbiguousSessionId { .. } => AMBIGUOUS_SESSION_ID,
+        TurlError::InvalidJsonLine { .. } => INVALID_JSON_LINE,
+        TurlError::Io { .. } => IO_ERROR,
+        TurlError::Serialize { .. } => SERIALIZE_ERROR,
+        TurlError::InvalidQuery { .. } => INVALID_QUERY,
+    };
+
+    RpcError {
+        code,
+        message: error.to_string(),
+    }
+}
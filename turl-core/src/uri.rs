@@ -0,0 +1,46 @@
+use crate::error::{Result, TurlError};
+use crate::model::ProviderKind;
+
+/// A reference to a single thread, e.g. `codex://019c871c-...` or
+/// `claude://019c871c-...`.
+#[derive(Debug, Clone)]
+pub struct ThreadUri {
+    pub provider: ProviderKind,
+    pub session_id: String,
+}
+
+impl ThreadUri {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let (scheme, rest) = raw.split_once("://").ok_or_else(|| TurlError::InvalidUri {
+            uri: raw.to_string(),
+            reason: "missing `://` separator".to_string(),
+        })?;
+
+        let provider = match scheme {
+            "codex" => ProviderKind::Codex,
+            "claude" => ProviderKind::Claude,
+            other => {
+                return Err(TurlError::InvalidUri {
+                    uri: raw.to_string(),
+                    reason: format!("unknown provider scheme `{other}`"),
+                });
+            }
+        };
+
+        if rest.is_empty() {
+            return Err(TurlError::InvalidUri {
+                uri: raw.to_string(),
+                reason: "missing session id".to_string(),
+            });
+        }
+
+        Ok(Self {
+            provider,
+            session_id: rest.to_string(),
+        })
+    }
+
+    pub fn as_string(&self) -> String {
+        format!("{}://{}", self.provider, self.session_id)
+    }
+}